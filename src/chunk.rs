@@ -1,8 +1,9 @@
-use std::array::TryFromSliceError;
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
+use std::io::{self, Read, Write};
 use std::str::{from_utf8, Utf8Error};
 
 use crate::chunk_type::ChunkType;
+use crate::codec::{Decode, Encode, Reader, Writer};
 use crate::Error;
 
 use crc::crc32::checksum_ieee;
@@ -15,9 +16,19 @@ pub struct Chunk {
     crc: u32,
 }
 
+// The CRC covers the chunk type and the data, per the PNG spec. Both
+// `Chunk::new` and `Chunk::try_from_verified` must use this same formula,
+// or a chunk built through the constructor would fail its own
+// verification.
+fn compute_crc(chunk_type: &ChunkType, chunk_data: &[u8]) -> u32 {
+    let mut crc_input = chunk_type.bytes().to_vec();
+    crc_input.extend_from_slice(chunk_data);
+    checksum_ieee(&crc_input)
+}
+
 impl Chunk {
     pub fn new(chunk_type: ChunkType, chunk_data: Vec<u8>) -> Self {
-        let crc = checksum_ieee(chunk_data.as_slice());
+        let crc = compute_crc(&chunk_type, &chunk_data);
         Self {
             length: chunk_data.len() as u32,
             chunk_type,
@@ -37,38 +48,92 @@ impl Chunk {
     pub fn crc(&self) -> u32 {
         self.crc
     }
+
+    /// Encrypts `plaintext` with a key derived from `passphrase` and stores
+    /// the sealed output as the chunk data, so the hidden message is
+    /// unreadable even if the chunk is discovered. See [`crate::crypto`].
+    pub fn new_encrypted(chunk_type: ChunkType, plaintext: &[u8], passphrase: &str) -> Self {
+        let chunk_data = crate::crypto::encrypt(plaintext, passphrase);
+        Self::new(chunk_type, chunk_data)
+    }
+
+    /// Decrypts chunk data previously produced by [`Chunk::new_encrypted`].
+    /// Returns `Error::DecryptionFailed` if `passphrase` is wrong or the
+    /// data has been tampered with.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Vec<u8>, Error> {
+        crate::crypto::decrypt(&self.chunk_data, passphrase)
+    }
+
+    /// Signs `data` with `secret_key` and stores it alongside the
+    /// signature as the chunk data, so a reader can later verify who
+    /// produced this chunk. See [`crate::signature`].
+    pub fn sign(chunk_type: ChunkType, data: Vec<u8>, secret_key: &secp256k1::SecretKey) -> Self {
+        let chunk_data = crate::signature::sign(&chunk_type, &data, secret_key);
+        Self::new(chunk_type, chunk_data)
+    }
+
+    /// Verifies a chunk produced by [`Chunk::sign`] against a known
+    /// `public_key`. Returns `Error::SignatureInvalid` if the signature
+    /// doesn't check out against that key, including if the data has been
+    /// tampered with since it was signed.
+    pub fn verify(&self, public_key: &secp256k1::PublicKey) -> Result<(), Error> {
+        crate::signature::verify(&self.chunk_type, &self.chunk_data, public_key)?;
+        Ok(())
+    }
+
+    /// Recovers the signer's public key from a chunk produced by
+    /// [`Chunk::sign`], without needing the key to be known ahead of time.
+    /// Unlike `verify`, this mode alone cannot detect tampering: see
+    /// [`crate::signature::recover`].
+    pub fn recover_signer(&self) -> Result<secp256k1::PublicKey, Error> {
+        let (_, public_key) = crate::signature::recover(&self.chunk_type, &self.chunk_data)?;
+        Ok(public_key)
+    }
     pub fn data_as_string(&self) -> Result<&str, Utf8Error> {
         from_utf8(self.chunk_data.as_slice())
     }
     pub fn as_bytes(&self) -> Vec<u8> {
-        let mut to_return = Vec::new();
-        to_return.append(&mut self.length.to_be_bytes().to_vec());
-        to_return.append(&mut self.chunk_type.bytes().to_vec());
-        to_return.append(&mut self.chunk_data.clone());
-        to_return.append(&mut self.crc.to_be_bytes().to_vec());
-        to_return
+        let mut w = Writer::new();
+        self.encode(&mut w);
+        w.into_vec()
     }
-}
 
-impl TryFrom<&[u8]> for Chunk {
-    type Error = Error;
-    fn try_from(mut value: &[u8]) -> Result<Self, Self::Error> {
-        // Reads the input from the first 4 bytes of value to get the length of the data
-        let length = read_be_u32(&mut value)?;
+    /// Reads a single chunk from `reader`, pulling the length, type, data and
+    /// crc fields in sequence. Like `TryFrom<&[u8]>`, this never indexes
+    /// past the end of the input: a truncated stream surfaces as
+    /// `Error::Io` wrapping an `UnexpectedEof`, rather than a panic.
+    ///
+    /// The declared length comes straight from `reader`, so it must not be
+    /// trusted to size an allocation up front (a socket peer could claim a
+    /// multi-gigabyte chunk). Instead `chunk_data` is filled via `take`,
+    /// which reads at most `length` bytes incrementally.
+    pub fn read_chunk<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut length_bytes = [0; 4];
+        reader.read_exact(&mut length_bytes)?;
+        let length = u32::from_be_bytes(length_bytes);
 
-        // Reads the chunk_type from the next 4 bytes of value
-        let chunk_type = match ChunkType::try_from([value[0], value[1], value[2], value[3]]) {
+        let mut type_bytes = [0; 4];
+        reader.read_exact(&mut type_bytes)?;
+        let chunk_type = match ChunkType::try_from(type_bytes) {
             Ok(t) => t,
             Err(_) => return Err(Error::InvalidByte),
         };
 
-        // Removes the first 4 bytes, as they're used for the chunk_type
-        value = value.split_at(std::mem::size_of::<u32>()).1;
+        let mut chunk_data = Vec::new();
+        reader
+            .by_ref()
+            .take(length as u64)
+            .read_to_end(&mut chunk_data)?;
+        if chunk_data.len() != length as usize {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "reader ended before the declared chunk length was reached",
+            )));
+        }
 
-        // Gets the last 4 bytes for the crc, and the remaining bytes for the data itself
-        let (chunk_data, mut input) = value.split_at(value.len() - 4);
-        let chunk_data = chunk_data.to_vec();
-        let crc = read_be_u32(&mut input)?;
+        let mut crc_bytes = [0; 4];
+        reader.read_exact(&mut crc_bytes)?;
+        let crc = u32::from_be_bytes(crc_bytes);
 
         Ok(Self {
             length,
@@ -77,15 +142,79 @@ impl TryFrom<&[u8]> for Chunk {
             crc,
         })
     }
+
+    /// Serializes the chunk directly into `writer`, without building up an
+    /// intermediate `Vec` the way `as_bytes` does.
+    pub fn write_chunk<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.length.to_be_bytes())?;
+        writer.write_all(&self.chunk_type.bytes())?;
+        writer.write_all(&self.chunk_data)?;
+        writer.write_all(&self.crc.to_be_bytes())?;
+        Ok(())
+    }
+
+    /// Decodes a chunk the same way as `TryFrom<&[u8]>`, but additionally
+    /// recomputes the CRC over the chunk type and data and checks it
+    /// against the stored value, and checks that `value` holds exactly one
+    /// encoded chunk with no trailing bytes left over. Use this when
+    /// reading chunks from an untrusted or possibly-corrupt source; the
+    /// unchecked `TryFrom` path remains available for tooling that
+    /// intentionally inspects damaged files.
+    pub fn try_from_verified(value: &[u8]) -> Result<Self, Error> {
+        let chunk = Self::try_from(value)?;
+
+        if chunk.encoded_len() != value.len() {
+            return Err(Error::LengthMismatch {
+                expected: chunk.encoded_len() as u32,
+                found: value.len() as u32,
+            });
+        }
+
+        let computed_crc = compute_crc(&chunk.chunk_type, &chunk.chunk_data);
+
+        if computed_crc != chunk.crc {
+            return Err(Error::CrcMismatch {
+                expected: chunk.crc,
+                found: computed_crc,
+            });
+        }
+
+        Ok(chunk)
+    }
 }
 
-// Converts a &mut &[u8] to a Result<u32, ...>, and removes the first 4 bytes from the input
-fn read_be_u32(input: &mut &[u8]) -> Result<u32, Error> {
-    let (int_bytes, rest) = input.split_at(std::mem::size_of::<u32>());
-    *input = rest;
-    return match int_bytes.try_into() {
-        Ok(t) => Ok(u32::from_be_bytes(t)),
-        Err(_) => Err(Error::FailedConversion),
+impl Encode for Chunk {
+    fn encoded_len(&self) -> usize {
+        4 + self.chunk_type.encoded_len() + self.chunk_data.len() + 4
+    }
+    fn encode(&self, w: &mut Writer) {
+        w.write_u32_be(self.length);
+        self.chunk_type.encode(w);
+        w.write_bytes(&self.chunk_data);
+        w.write_u32_be(self.crc);
+    }
+}
+
+impl Decode for Chunk {
+    fn decode(r: &mut Reader) -> Result<Self, Error> {
+        let length = r.read_u32_be()?;
+        let chunk_type = ChunkType::decode(r)?;
+        let chunk_data = r.read_bytes(length as usize)?.to_vec();
+        let crc = r.read_u32_be()?;
+
+        Ok(Self {
+            length,
+            chunk_type,
+            chunk_data,
+            crc,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for Chunk {
+    type Error = Error;
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::decode(&mut Reader::new(value))
     }
 }
 
@@ -186,7 +315,167 @@ mod tests {
 
         let chunk = Chunk::try_from(chunk_data.as_ref());
 
-        assert!(!chunk.is_err());
+        assert!(chunk.is_ok());
+    }
+
+    #[test]
+    fn test_signed_chunk_roundtrip() {
+        let secp = secp256k1::Secp256k1::new();
+        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let message = b"This is where your secret message will be!".to_vec();
+
+        let chunk = Chunk::sign(chunk_type, message, &secret_key);
+
+        assert!(chunk.verify(&public_key).is_ok());
+        assert_eq!(chunk.recover_signer().unwrap(), public_key);
+    }
+
+    #[test]
+    fn test_tampered_signed_chunk_fails_verify() {
+        let secp = secp256k1::Secp256k1::new();
+        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let message = b"This is where your secret message will be!".to_vec();
+
+        let mut chunk = Chunk::sign(chunk_type, message, &secret_key);
+        chunk.chunk_data[0] ^= 0xff;
+
+        assert!(chunk.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_signed_chunk_fails_verify_against_wrong_key() {
+        let secp = secp256k1::Secp256k1::new();
+        let (secret_key, _) = secp.generate_keypair(&mut rand::thread_rng());
+        let (_, other_public_key) = secp.generate_keypair(&mut rand::thread_rng());
+
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let message = b"This is where your secret message will be!".to_vec();
+
+        let chunk = Chunk::sign(chunk_type, message, &secret_key);
+
+        assert!(chunk.verify(&other_public_key).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_chunk_roundtrip() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let message = b"This is where your secret message will be!";
+
+        let chunk = Chunk::new_encrypted(chunk_type, message, "correct horse battery staple");
+        let decrypted = chunk.decrypt("correct horse battery staple").unwrap();
+
+        assert_eq!(decrypted, message);
+    }
+
+    #[test]
+    fn test_encrypted_chunk_wrong_passphrase_fails() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let message = b"This is where your secret message will be!";
+
+        let chunk = Chunk::new_encrypted(chunk_type, message, "correct horse battery staple");
+
+        assert!(chunk.decrypt("wrong passphrase").is_err());
+    }
+
+    #[test]
+    fn test_chunk_new_verifies_its_own_output() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let chunk = Chunk::new(
+            chunk_type,
+            b"This is where your secret message will be!".to_vec(),
+        );
+
+        let verified = Chunk::try_from_verified(chunk.as_bytes().as_ref()).unwrap();
+
+        assert_eq!(verified.crc(), chunk.crc());
+    }
+
+    #[test]
+    fn test_valid_chunk_from_bytes_verified() {
+        let chunk = testing_chunk().unwrap();
+        let verified = Chunk::try_from_verified(chunk.as_bytes().as_ref()).unwrap();
+
+        assert_eq!(verified.crc(), chunk.crc());
+    }
+
+    #[test]
+    fn test_trailing_garbage_fails_verified() {
+        let chunk = testing_chunk().unwrap();
+        let mut bytes = chunk.as_bytes();
+        bytes.push(0);
+
+        assert!(matches!(
+            Chunk::try_from_verified(bytes.as_ref()),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_chunk_from_bytes_verified() {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656333;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        let chunk = Chunk::try_from_verified(chunk_data.as_ref());
+
+        assert!(chunk.is_err());
+    }
+
+    #[test]
+    fn test_read_chunk_from_reader() {
+        let chunk = testing_chunk().unwrap();
+        let bytes = chunk.as_bytes();
+
+        let read_back = Chunk::read_chunk(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(read_back.length(), chunk.length());
+        assert_eq!(read_back.chunk_type(), chunk.chunk_type());
+        assert_eq!(read_back.data(), chunk.data());
+        assert_eq!(read_back.crc(), chunk.crc());
+    }
+
+    #[test]
+    fn test_write_chunk_to_writer() {
+        let chunk = testing_chunk().unwrap();
+
+        let mut written = Vec::new();
+        chunk.write_chunk(&mut written).unwrap();
+
+        assert_eq!(written, chunk.as_bytes());
+    }
+
+    #[test]
+    fn test_read_chunk_rejects_lying_length_without_huge_allocation() {
+        // Declares a ~4GB chunk length but only actually provides a
+        // handful of bytes, as a malicious socket peer would.
+        let mut bytes = u32::MAX.to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"RuSt");
+        bytes.extend_from_slice(b"short");
+
+        assert!(Chunk::read_chunk(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_read_chunk_truncated_input_is_not_eof_panic() {
+        let chunk = testing_chunk().unwrap();
+        let bytes = chunk.as_bytes();
+        let truncated = &bytes[..bytes.len() - 1];
+
+        assert!(Chunk::read_chunk(&mut &truncated[..]).is_err());
     }
 
     #[test]
@@ -1,6 +1,7 @@
 use std::convert::{TryFrom, TryInto};
 use std::{array::TryFromSliceError, str::FromStr};
 
+use crate::codec::{Decode, Encode, Reader, Writer};
 use crate::Error;
 
 #[derive(PartialEq, Debug)]
@@ -28,7 +29,7 @@ impl ChunkType {
         self.chunk_type[3].is_ascii_lowercase()
     }
     pub fn to_string(&self) -> &str {
-        return std::str::from_utf8(&self.chunk_type).unwrap();
+        std::str::from_utf8(&self.chunk_type).unwrap()
     }
 }
 
@@ -52,6 +53,21 @@ impl TryFrom<[u8; 4]> for ChunkType {
     }
 }
 
+impl Encode for ChunkType {
+    fn encoded_len(&self) -> usize {
+        4
+    }
+    fn encode(&self, w: &mut Writer) {
+        w.write_bytes(&self.chunk_type);
+    }
+}
+
+impl Decode for ChunkType {
+    fn decode(r: &mut Reader) -> Result<Self, Error> {
+        Self::try_from(r.read_array::<4>()?)
+    }
+}
+
 #[allow(unused_variables)]
 #[cfg(test)]
 mod tests {
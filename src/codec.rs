@@ -0,0 +1,117 @@
+use std::convert::TryInto;
+
+use crate::Error;
+
+/// A reusable, length-checked codec for `ChunkType`, `Chunk` and `Png`,
+/// replacing the ad-hoc `TryFrom<&[u8]>`/`as_bytes` pairs that used to
+/// duplicate bounds checking (and panic on short input) in each type.
+pub trait Encode {
+    /// The exact number of bytes `encode` will write, so callers can
+    /// pre-size output buffers.
+    fn encoded_len(&self) -> usize;
+    fn encode(&self, w: &mut Writer);
+}
+
+pub trait Decode: Sized {
+    fn decode(r: &mut Reader) -> Result<Self, Error>;
+}
+
+/// A byte slice with a cursor, offering checked primitives that return
+/// `Error::UnexpectedEof` instead of panicking on short input.
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        let end = self.position.checked_add(n).ok_or(Error::UnexpectedEof)?;
+        if end > self.bytes.len() {
+            return Err(Error::UnexpectedEof);
+        }
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        self.read_bytes(N)?.try_into().map_err(|_| Error::UnexpectedEof)
+    }
+
+    pub fn read_u32_be(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(self.read_array::<4>()?))
+    }
+}
+
+/// An append-only output buffer for `Encode` implementations.
+pub struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self { bytes: Vec::new() }
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn write_u32_be(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl Default for Writer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reader_reads_fields_in_order() {
+        let bytes = [0, 0, 0, 42, 1, 2, 3, 4];
+        let mut r = Reader::new(&bytes);
+
+        assert_eq!(r.read_u32_be().unwrap(), 42);
+        assert_eq!(r.read_array::<4>().unwrap(), [1, 2, 3, 4]);
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn test_reader_rejects_short_input() {
+        let bytes = [0, 0, 0];
+        let mut r = Reader::new(&bytes);
+
+        assert!(r.read_u32_be().is_err());
+    }
+
+    #[test]
+    fn test_writer_roundtrips_with_reader() {
+        let mut w = Writer::new();
+        w.write_u32_be(7);
+        w.write_bytes(&[9, 8, 7]);
+
+        let bytes = w.into_vec();
+        let mut r = Reader::new(&bytes);
+
+        assert_eq!(r.read_u32_be().unwrap(), 7);
+        assert_eq!(r.read_bytes(3).unwrap(), [9, 8, 7]);
+    }
+}
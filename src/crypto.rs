@@ -0,0 +1,91 @@
+use chacha20poly1305::aead::{Aead, NewAead};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hmac::Hmac;
+use rand::RngCore;
+use sha2::Sha256;
+
+use crate::Error;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const PBKDF2_ROUNDS: u32 = 100_000;
+
+// Derives a 32-byte ChaCha20-Poly1305 key from a passphrase and a random
+// salt using PBKDF2-HMAC-SHA256.
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; KEY_LEN] {
+    let mut key = [0; KEY_LEN];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    key
+}
+
+/// Encrypts `plaintext` under `passphrase`, returning
+/// `salt(16) || nonce(12) || ciphertext || tag(16)`.
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Vec<u8> {
+    let mut salt = [0; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+
+    let mut nonce_bytes = [0; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(passphrase, &salt);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .expect("chacha20poly1305 encryption does not fail");
+
+    let mut sealed = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce_bytes);
+    sealed.extend_from_slice(&ciphertext);
+    sealed
+}
+
+/// Reverses [`encrypt`], re-deriving the key from `passphrase` and the
+/// stored salt, then verifying the Poly1305 tag. Returns
+/// `Error::DecryptionFailed` if the passphrase is wrong or the data has
+/// been tampered with.
+pub fn decrypt(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>, Error> {
+    if sealed.len() < SALT_LEN + NONCE_LEN {
+        return Err(Error::DecryptionFailed);
+    }
+
+    let (salt, rest) = sealed.split_at(SALT_LEN);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = derive_key(passphrase, salt);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| Error::DecryptionFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let plaintext = b"This is where your secret message will be!";
+        let sealed = encrypt(plaintext, "correct horse battery staple");
+
+        assert_eq!(
+            decrypt(&sealed, "correct horse battery staple").unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn test_decrypt_rejects_too_short_input() {
+        let sealed = vec![0; SALT_LEN + NONCE_LEN - 1];
+
+        assert!(matches!(
+            decrypt(&sealed, "correct horse battery staple"),
+            Err(Error::DecryptionFailed)
+        ));
+    }
+}
@@ -1,12 +1,21 @@
 #![allow(non_snake_case)]
 pub mod chunk;
 pub mod chunk_type;
+pub mod codec;
+pub mod crypto;
 pub mod png;
+pub mod signature;
 
 #[derive(Debug)]
 pub enum Error {
 	InvalidByte,
 	FailedConversion,
+	Io(std::io::Error),
+	CrcMismatch { expected: u32, found: u32 },
+	LengthMismatch { expected: u32, found: u32 },
+	DecryptionFailed,
+	SignatureInvalid,
+	UnexpectedEof,
 }
 
 impl std::fmt::Display for Error {
@@ -14,6 +23,26 @@ impl std::fmt::Display for Error {
         match self {
             Error::InvalidByte => write!(f, "The byte given was invalid ascii"),
             Error::FailedConversion => write!(f, "A conversion failed"),
+            Error::Io(e) => write!(f, "An IO error occurred: {}", e),
+            Error::CrcMismatch { expected, found } => write!(
+                f,
+                "CRC mismatch: expected {}, but computed {}",
+                expected, found
+            ),
+            Error::LengthMismatch { expected, found } => write!(
+                f,
+                "Length mismatch: chunk encodes to {} bytes, but input was {} bytes",
+                expected, found
+            ),
+            Error::DecryptionFailed => write!(f, "Decryption failed: wrong passphrase or tampered data"),
+            Error::SignatureInvalid => write!(f, "The chunk's signature did not verify"),
+            Error::UnexpectedEof => write!(f, "Ran out of bytes while decoding"),
         }
     }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
 }
\ No newline at end of file
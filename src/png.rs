@@ -0,0 +1,149 @@
+use std::io::{self, Cursor, Read, Write};
+
+use crate::chunk::Chunk;
+use crate::codec::{Decode, Encode, Reader, Writer};
+use crate::Error;
+
+/// The 8-byte sequence every PNG file starts with.
+pub const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+#[derive(Debug)]
+pub struct Png {
+    header: [u8; 8],
+    chunks: Vec<Chunk>,
+}
+
+impl Png {
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Self {
+        Self {
+            header: STANDARD_HEADER,
+            chunks,
+        }
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    /// Reads a whole PNG from `reader`, pulling the header and then chunks
+    /// one at a time with `Chunk::read_chunk` until the reader is exhausted.
+    /// A stream that ends mid-chunk surfaces as `Error::Io` rather than
+    /// panicking.
+    pub fn read_png<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut header = [0; 8];
+        reader.read_exact(&mut header)?;
+        if header != STANDARD_HEADER {
+            return Err(Error::InvalidByte);
+        }
+
+        let mut chunks = Vec::new();
+        loop {
+            // Peek a single byte to tell a clean EOF between chunks apart
+            // from a chunk that's truncated partway through.
+            let mut first_byte = [0; 1];
+            if reader.read(&mut first_byte)? == 0 {
+                break;
+            }
+            let mut rest = Cursor::new(first_byte).chain(reader.by_ref());
+            chunks.push(Chunk::read_chunk(&mut rest)?);
+        }
+
+        Ok(Self { header, chunks })
+    }
+
+    /// Serializes the header followed by every chunk directly into `writer`.
+    pub fn write_png<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.header)?;
+        for chunk in &self.chunks {
+            chunk.write_chunk(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Encode for Png {
+    fn encoded_len(&self) -> usize {
+        self.header.len()
+            + self
+                .chunks
+                .iter()
+                .map(Chunk::encoded_len)
+                .sum::<usize>()
+    }
+    fn encode(&self, w: &mut Writer) {
+        w.write_bytes(&self.header);
+        for chunk in &self.chunks {
+            chunk.encode(w);
+        }
+    }
+}
+
+impl Decode for Png {
+    /// Decodes a whole PNG from an in-memory buffer: the header, then
+    /// chunks in sequence until the buffer is exhausted. Composes with
+    /// `Chunk::decode`/`ChunkType::decode`, so all bounds checking lives in
+    /// `Reader` rather than being duplicated per type.
+    fn decode(r: &mut Reader) -> Result<Self, Error> {
+        let header = r.read_array::<8>()?;
+        if header != STANDARD_HEADER {
+            return Err(Error::InvalidByte);
+        }
+
+        let mut chunks = Vec::new();
+        while !r.is_empty() {
+            chunks.push(Chunk::decode(r)?);
+        }
+
+        Ok(Self { header, chunks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::chunk_type::ChunkType;
+
+    fn testing_png() -> Png {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let chunk = Chunk::new(chunk_type, b"This is where your secret message will be!".to_vec());
+        Png::from_chunks(vec![chunk])
+    }
+
+    #[test]
+    fn test_png_encode_decode_roundtrip() {
+        let png = testing_png();
+
+        let mut w = Writer::new();
+        png.encode(&mut w);
+        let bytes = w.into_vec();
+
+        assert_eq!(bytes.len(), png.encoded_len());
+
+        let decoded = Png::decode(&mut Reader::new(&bytes)).unwrap();
+        assert_eq!(decoded.chunks().len(), png.chunks().len());
+        assert_eq!(decoded.chunks()[0].crc(), png.chunks()[0].crc());
+    }
+
+    #[test]
+    fn test_read_write_png_roundtrip() {
+        let png = testing_png();
+
+        let mut written = Vec::new();
+        png.write_png(&mut written).unwrap();
+
+        let read_back = Png::read_png(&mut written.as_slice()).unwrap();
+        assert_eq!(read_back.chunks().len(), png.chunks().len());
+    }
+
+    #[test]
+    fn test_read_png_rejects_bad_header() {
+        let bytes = [0; 8];
+        assert!(Png::read_png(&mut bytes.as_slice()).is_err());
+    }
+}
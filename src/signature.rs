@@ -0,0 +1,145 @@
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+use sha2::{Digest, Sha256};
+
+use crate::chunk_type::ChunkType;
+use crate::Error;
+
+const SIGNATURE_LEN: usize = 64;
+const RECOVERY_ID_LEN: usize = 1;
+
+// The signed message is SHA-256 over the chunk type followed by the data,
+// so a signature is bound to both.
+fn digest(chunk_type: &ChunkType, data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(chunk_type.bytes());
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Signs `data` under `chunk_type` with `secret_key`, returning
+/// `data || signature(64) || recovery_id(1)`, ready to be used as chunk
+/// data. The recovery id lets [`recover`] recover the signer's public key
+/// without it having to be transmitted alongside the chunk.
+pub fn sign(chunk_type: &ChunkType, data: &[u8], secret_key: &SecretKey) -> Vec<u8> {
+    let secp = Secp256k1::signing_only();
+    let message = Message::from_slice(&digest(chunk_type, data)).expect("digest is 32 bytes");
+
+    let (recovery_id, signature) = secp
+        .sign_ecdsa_recoverable(&message, secret_key)
+        .serialize_compact();
+
+    let mut signed = Vec::with_capacity(data.len() + SIGNATURE_LEN + RECOVERY_ID_LEN);
+    signed.extend_from_slice(data);
+    signed.extend_from_slice(&signature);
+    signed.push(recovery_id.to_i32() as u8);
+    signed
+}
+
+fn parse(signed: &[u8]) -> Result<(&[u8], RecoverableSignature), Error> {
+    if signed.len() < SIGNATURE_LEN + RECOVERY_ID_LEN {
+        return Err(Error::SignatureInvalid);
+    }
+
+    let (data, rest) = signed.split_at(signed.len() - SIGNATURE_LEN - RECOVERY_ID_LEN);
+    let (signature_bytes, recovery_byte) = rest.split_at(SIGNATURE_LEN);
+
+    let recovery_id =
+        RecoveryId::from_i32(recovery_byte[0] as i32).map_err(|_| Error::SignatureInvalid)?;
+    let signature = RecoverableSignature::from_compact(signature_bytes, recovery_id)
+        .map_err(|_| Error::SignatureInvalid)?;
+
+    Ok((data, signature))
+}
+
+/// Verifies a payload produced by [`sign`] against a known `public_key`.
+/// Returns the original data, or `Error::SignatureInvalid` if the
+/// signature doesn't check out against that key (including if `data` has
+/// been tampered with since it was signed).
+pub fn verify<'a>(
+    chunk_type: &ChunkType,
+    signed: &'a [u8],
+    public_key: &PublicKey,
+) -> Result<&'a [u8], Error> {
+    let (data, signature) = parse(signed)?;
+
+    let secp = Secp256k1::verification_only();
+    let message = Message::from_slice(&digest(chunk_type, data)).expect("digest is 32 bytes");
+
+    secp.verify_ecdsa(&message, &signature.to_standard(), public_key)
+        .map_err(|_| Error::SignatureInvalid)?;
+
+    Ok(data)
+}
+
+/// Recovers the signer's public key from a payload produced by [`sign`],
+/// without needing the key to be known ahead of time. Note that unlike
+/// [`verify`], this cannot by itself detect tampering: recovery always
+/// succeeds with *some* key for any well-formed signature, since the
+/// recovered key is derived from the digest of the data as it is now.
+/// Callers that need tamper-evidence should check the recovered key
+/// against an expected one, or use [`verify`] directly.
+pub fn recover(chunk_type: &ChunkType, signed: &[u8]) -> Result<(Vec<u8>, PublicKey), Error> {
+    let (data, signature) = parse(signed)?;
+
+    let secp = Secp256k1::verification_only();
+    let message = Message::from_slice(&digest(chunk_type, data)).expect("digest is 32 bytes");
+
+    let public_key = secp
+        .recover_ecdsa(&message, &signature)
+        .map_err(|_| Error::SignatureInvalid)?;
+
+    Ok((data.to_vec(), public_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::chunk_type::ChunkType;
+
+    #[test]
+    fn test_verify_rejects_truncated_signed_buffer() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let secp = Secp256k1::new();
+        let (_, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+
+        // Shorter than a signature plus recovery byte could ever be.
+        let signed = vec![0; SIGNATURE_LEN];
+
+        assert!(matches!(
+            verify(&chunk_type, &signed, &public_key),
+            Err(Error::SignatureInvalid)
+        ));
+    }
+
+    #[test]
+    fn test_recover_rejects_truncated_signed_buffer() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let signed = vec![0; SIGNATURE_LEN];
+
+        assert!(matches!(
+            recover(&chunk_type, &signed),
+            Err(Error::SignatureInvalid)
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_invalid_recovery_id() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let secp = Secp256k1::new();
+        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+
+        let data = b"hello".to_vec();
+        let mut signed = sign(&chunk_type, &data, &secret_key);
+
+        // Valid recovery ids are 0-3; this byte is out of range.
+        *signed.last_mut().unwrap() = 4;
+
+        assert!(matches!(
+            verify(&chunk_type, &signed, &public_key),
+            Err(Error::SignatureInvalid)
+        ));
+    }
+}